@@ -0,0 +1,112 @@
+//! A compact, self-contained export of a resolved dependency graph, suitable
+//! for embedding in a binary and recovering later (e.g. for build
+//! provenance / SBOM-style auditing).
+
+use std::collections::HashMap;
+
+use {Metadata, Package};
+
+/// A single package entry in an [`AuditReport`](struct.AuditReport.html)
+#[derive(Clone, Serialize, Debug)]
+pub struct AuditPackage {
+    /// An opaque identifier for the package, matching `Package::id`
+    pub id: String,
+    /// Name as given in the `Cargo.toml`
+    pub name: String,
+    /// Exact resolved version
+    pub version: String,
+    /// The registry or git source this package was fetched from, or `None`
+    /// for a path dependency (such as a workspace member).
+    pub source: Option<String>,
+    /// Whether this package is a workspace member (as opposed to a
+    /// dependency pulled in from a registry or git source)
+    pub root: bool,
+    /// Opaque identifiers of this package's direct dependencies
+    pub dependencies: Vec<String>,
+}
+
+/// A self-contained, topologically ordered export of a resolved dependency
+/// graph, suitable for embedding in a binary. Package ids are copied
+/// verbatim, so the ids of path packages (such as workspace members) still
+/// carry their `path+file://` location.
+#[derive(Clone, Serialize, Debug)]
+pub struct AuditReport {
+    /// Packages, deduplicated by id, ordered so that workspace members (the
+    /// roots) come first, followed by their dependencies in dependency
+    /// order.
+    pub packages: Vec<AuditPackage>,
+}
+
+/// Builds an [`AuditReport`](struct.AuditReport.html) from resolved
+/// `metadata`.
+///
+/// Requires `metadata.resolve` to be present, i.e. that the `cargo metadata`
+/// invocation was not run with `--no-deps`.
+pub fn audit_report(metadata: &Metadata) -> Option<AuditReport> {
+    let resolve = metadata.resolve.as_ref()?;
+    let packages: HashMap<&str, &Package> = metadata
+        .packages
+        .iter()
+        .map(|package| (package.id.as_str(), package))
+        .collect();
+    let roots: ::std::collections::HashSet<&str> =
+        metadata.workspace_members.iter().map(String::as_str).collect();
+
+    let dependencies: HashMap<&str, Vec<String>> = resolve
+        .nodes
+        .iter()
+        .map(|node| (node.id.as_str(), node.dependencies.clone()))
+        .collect();
+
+    let mut ordered = Vec::with_capacity(resolve.nodes.len());
+    let mut visited = ::std::collections::HashSet::new();
+
+    fn visit<'a>(
+        id: &'a str,
+        packages: &HashMap<&'a str, &'a Package>,
+        dependencies: &HashMap<&'a str, Vec<String>>,
+        roots: &::std::collections::HashSet<&'a str>,
+        visited: &mut ::std::collections::HashSet<String>,
+        ordered: &mut Vec<AuditPackage>,
+    ) {
+        if !visited.insert(id.to_string()) {
+            return;
+        }
+        let package = match packages.get(id) {
+            Some(package) => package,
+            None => return,
+        };
+        if let Some(deps) = dependencies.get(id) {
+            for dep in deps {
+                visit(dep, packages, dependencies, roots, visited, ordered);
+            }
+        }
+        ordered.push(AuditPackage {
+            id: package.id.clone(),
+            name: package.name.clone(),
+            version: package.version.clone(),
+            source: package.source.clone(),
+            root: roots.contains(id),
+            dependencies: dependencies.get(id).cloned().unwrap_or_default(),
+        });
+    }
+
+    let mut root_ids: Vec<&str> = roots.iter().cloned().collect();
+    root_ids.sort();
+    for id in root_ids {
+        visit(id, &packages, &dependencies, &roots, &mut visited, &mut ordered);
+    }
+    // Anything not transitively reachable from a workspace member (this
+    // shouldn't normally happen, but keeps the export complete).
+    let mut remaining: Vec<&str> = packages.keys().cloned().filter(|id| !visited.contains(*id)).collect();
+    remaining.sort();
+    for id in remaining {
+        visit(id, &packages, &dependencies, &roots, &mut visited, &mut ordered);
+    }
+    // `ordered` lists every package after its dependencies; reverse it, then
+    // move the workspace members to the front (the sort is stable).
+    ordered.reverse();
+    ordered.sort_by_key(|package| !package.root);
+
+    Some(AuditReport { packages: ordered })
+}
@@ -0,0 +1,52 @@
+use semver::VersionReq;
+use serde::{Deserialize, Deserializer};
+
+/// A dependency of the main crate
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct Dependency {
+    /// Name as given in the `Cargo.toml`
+    pub name: String,
+    /// The required version
+    pub req: VersionReq,
+    /// The kind of dependency this is
+    #[serde(default, deserialize_with = "deserialize_kind")]
+    pub kind: DependencyKind,
+    /// Whether this dependency is required or optional
+    pub optional: bool,
+    /// Whether the default features in this dependency are used.
+    pub uses_default_features: bool,
+    /// The list of features enabled for this dependency.
+    pub features: Vec<String>,
+    /// The target this dependency is specific to.
+    pub target: Option<String>,
+}
+
+/// Dependencies can come in three kinds
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq, Hash, Default)]
+pub enum DependencyKind {
+    /// The 'normal' kind
+    #[serde(rename = "normal")]
+    #[default]
+    Normal,
+    /// Those used in tests only
+    #[serde(rename = "dev")]
+    Development,
+    /// Those used in build scripts only
+    #[serde(rename = "build")]
+    Build,
+    /// Some other kind of dependency that this version of the crate does
+    /// not know about
+    #[doc(hidden)]
+    #[serde(other)]
+    Unknown,
+}
+
+/// Cargo represents a normal dependency's `kind` as `null` rather than the
+/// string `"normal"`, which doesn't fit `DependencyKind` directly; read it as
+/// an `Option` and fall back to the default (`Normal`) kind.
+pub(crate) fn deserialize_kind<'de, D>(deserializer: D) -> Result<DependencyKind, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<DependencyKind>::deserialize(deserializer)?.unwrap_or_default())
+}
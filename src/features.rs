@@ -0,0 +1,58 @@
+//! Resolving the transitive closure of a package's Cargo features.
+
+use std::collections::BTreeSet;
+
+impl ::Package {
+    /// Computes the full set of this package's own features that end up
+    /// enabled, starting from `activated` (plus `"default"` if `default` is
+    /// `true`), by repeatedly expanding each enabled feature through the
+    /// `[features]` table until a fixpoint is reached.
+    ///
+    /// Entries of the form `"dep/feature"` or `"crate_name/feature"` enable
+    /// a feature on one of this package's dependencies; since that feature
+    /// lives in a different package's feature set, it is not part of this
+    /// package's own feature set and is not included in the returned set.
+    /// A name that isn't a key in this package's `[features]` table is
+    /// assumed to be the name of an optional dependency, which is likewise
+    /// a no-op for this package's own feature set and is not included in the
+    /// returned set.
+    pub fn resolve_features(&self, activated: &[&str], default: bool) -> BTreeSet<String> {
+        let mut enabled = BTreeSet::new();
+        let mut seen = BTreeSet::new();
+        let mut queue: Vec<String> = activated.iter().map(|s| s.to_string()).collect();
+        if default {
+            queue.push(String::from("default"));
+        }
+
+        while let Some(feature) = queue.pop() {
+            if !seen.insert(feature.clone()) {
+                continue;
+            }
+
+            if split_dep_feature(&feature).is_some() {
+                // The feature lives on the dependency's side; the optional
+                // dependency activation itself is not part of this
+                // package's own feature set.
+                continue;
+            }
+
+            // An unknown feature name is treated as the name of an optional
+            // dependency: enabling it is a no-op for this package's own
+            // feature set.
+            if let Some(implied) = self.features.get(&feature) {
+                enabled.insert(feature);
+                for next in implied {
+                    queue.push(next.clone());
+                }
+            }
+        }
+
+        enabled
+    }
+}
+
+/// Splits a `"dep/feature"` or `"crate_name/feature"` entry into its two
+/// halves, or returns `None` if `feature` doesn't contain a slash.
+fn split_dep_feature(feature: &str) -> Option<(&str, &str)> {
+    feature.split_once('/')
+}
@@ -16,19 +16,27 @@ extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 extern crate serde_json;
+extern crate semver;
 
 use std::collections::HashMap;
 use std::env;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::str::from_utf8;
 
 pub use errors::{Error, Result};
 pub use dependency::{Dependency, DependencyKind};
+pub use platform::target_matches;
+pub use audit::{audit_report, AuditPackage, AuditReport};
+pub use message::{parse_messages, Artifact, BuildFinished, BuildScript, FromCompiler, Message};
 
+mod audit;
 mod dependency;
+mod features;
+mod message;
+mod platform;
 
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Serialize, Debug)]
 /// Starting point for metadata returned by `cargo metadata`
 pub struct Metadata {
     /// A list of all crates referenced by this crate (and the crate itself)
@@ -41,23 +49,48 @@ pub struct Metadata {
     version: usize,
 }
 
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Serialize, Debug)]
 /// A dependency graph
 pub struct Resolve {
     /// Nodes in a dependencies graph
     pub nodes: Vec<Node>,
 }
 
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Serialize, Debug)]
 /// A node in a dependencies graph
 pub struct Node {
     /// An opaque identifier for a package
     pub id: String,
     /// List of opaque identifiers for this node's dependencies
     pub dependencies: Vec<String>,
+    /// List of this node's dependencies, with kind and target information
+    #[serde(default)]
+    pub deps: Vec<NodeDep>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+/// A dependency edge in a dependencies graph, resolved to a single package
+pub struct NodeDep {
+    /// The name of the dependency's library target, as seen from this node
+    pub name: String,
+    /// An opaque identifier for the package this edge resolves to
+    pub pkg: String,
+    /// The kinds (normal/dev/build) and targets under which this edge is active
+    #[serde(default)]
+    pub dep_kinds: Vec<DepKindInfo>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+/// The kind and target platform of a resolved dependency edge
+pub struct DepKindInfo {
+    /// The kind of dependency this edge is
+    #[serde(default, deserialize_with = "dependency::deserialize_kind")]
+    pub kind: DependencyKind,
+    /// The `cfg(...)` expression or target triple this edge is gated to, if any
+    pub target: Option<String>,
 }
 
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Serialize, Debug)]
 /// A crate
 pub struct Package {
     /// Name as given in the `Cargo.toml`
@@ -74,9 +107,35 @@ pub struct Package {
     features: HashMap<String, Vec<String>>,
     /// Path containing the `Cargo.toml`
     pub manifest_path: String,
+    /// The Rust edition of this package, e.g. `"2015"` or `"2018"`
+    #[serde(default = "edition_default")]
+    pub edition: String,
+    /// Short description as given in the `Cargo.toml`
+    #[serde(default)]
+    pub description: Option<String>,
+    /// License as given in the `Cargo.toml`
+    #[serde(default)]
+    pub license: Option<String>,
+    /// Path to a license file, relative to the `Cargo.toml`, as given in the
+    /// `Cargo.toml`
+    #[serde(default)]
+    pub license_file: Option<PathBuf>,
+    /// Authors as given in the `Cargo.toml`
+    #[serde(default)]
+    pub authors: Vec<String>,
+    /// Categories as given in the `Cargo.toml`
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Keywords as given in the `Cargo.toml`
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+fn edition_default() -> String {
+    String::from("2015")
 }
 
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Serialize, Debug)]
 /// A single target (lib, bin, example, ...) provided by a crate
 pub struct Target {
     /// Name as given in the `Cargo.toml` or generated from the file name
@@ -89,6 +148,13 @@ pub struct Target {
     pub crate_types: Vec<String>,
     /// Path to the main source file of the target
     pub src_path: String,
+    /// The Rust edition of this target, e.g. `"2015"` or `"2018"`
+    #[serde(default = "edition_default")]
+    pub edition: String,
+    /// Features required to build this target, as given by `required-features`
+    /// in the `Cargo.toml`
+    #[serde(default)]
+    pub required_features: Vec<String>,
 }
 
 mod errors {
@@ -0,0 +1,100 @@
+//! A parser for the line-delimited JSON that `cargo build`/`cargo check`
+//! emit when run with `--message-format=json`, as consumed by tools like
+//! rust-analyzer to discover built artifacts and build-script output.
+
+use std::io::BufRead;
+use std::path::PathBuf;
+
+use serde_json;
+
+use {Result, Target};
+
+/// A single message emitted by `cargo build --message-format=json`
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+pub enum Message {
+    /// A compiler artifact (an `.rlib`, binary, ...) was produced
+    CompilerArtifact(Artifact),
+    /// A diagnostic emitted by the compiler
+    CompilerMessage(FromCompiler),
+    /// A build script was executed
+    BuildScriptExecuted(BuildScript),
+    /// The build as a whole finished
+    BuildFinished(BuildFinished),
+    /// A message that this version of the crate doesn't know how to
+    /// interpret
+    #[serde(other)]
+    Unknown,
+}
+
+/// An artifact produced by the compiler, e.g. an executable or library
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct Artifact {
+    /// The package this artifact belongs to, as an opaque identifier
+    pub package_id: String,
+    /// The target that was built
+    pub target: Target,
+    /// The files produced by this build
+    pub filenames: Vec<PathBuf>,
+    /// Whether this artifact was already up to date (and thus not actually
+    /// rebuilt)
+    pub fresh: bool,
+}
+
+/// A diagnostic emitted by the compiler while building a package
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct FromCompiler {
+    /// The package the diagnostic was emitted for, as an opaque identifier
+    pub package_id: String,
+    /// The diagnostic as the compiler emitted it, kept as raw JSON (`Null`
+    /// if the message carried none)
+    #[serde(default)]
+    pub message: serde_json::Value,
+}
+
+/// Output of a build script (`build.rs`) run for a package
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct BuildScript {
+    /// The package this build script was run for, as an opaque identifier
+    pub package_id: String,
+    /// The directory the build script wrote its output to (`OUT_DIR`)
+    pub out_dir: PathBuf,
+    /// Native libraries the build script asked to be linked against
+    #[serde(default)]
+    pub linked_libs: Vec<String>,
+    /// Search paths the build script asked to be searched for linked
+    /// libraries
+    #[serde(default)]
+    pub linked_paths: Vec<String>,
+    /// `cfg` flags the build script asked to be enabled
+    #[serde(default)]
+    pub cfgs: Vec<String>,
+    /// Environment variables the build script asked to be set
+    #[serde(default)]
+    pub env: Vec<(String, String)>,
+}
+
+/// Emitted once the whole build finishes
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct BuildFinished {
+    /// Whether the build succeeded
+    pub success: bool,
+}
+
+/// Parses the line-delimited JSON emitted by `cargo build
+/// --message-format=json` (or `cargo check`/`cargo test` with the same
+/// flag), tolerating interleaved non-JSON lines (such as output from a
+/// build script forwarded to stdout).
+pub fn parse_messages<R: BufRead>(reader: R) -> impl Iterator<Item = Result<Message>> {
+    reader.lines().filter_map(|line| {
+        let line = match line {
+            Ok(line) => line,
+            Err(err) => return Some(Err(err.into())),
+        };
+        let line = line.trim();
+        if line.is_empty() || !line.starts_with('{') {
+            return None;
+        }
+        Some(serde_json::from_str(line).map_err(Into::into))
+    })
+}
@@ -0,0 +1,214 @@
+//! A small evaluator for the `cfg(...)` expressions and target triples that
+//! appear in `Dependency::target`.
+
+use std::collections::{HashMap, HashSet};
+
+use Dependency;
+
+/// A parsed `cfg(...)` expression
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum CfgExpr {
+    All(Vec<CfgExpr>),
+    Any(Vec<CfgExpr>),
+    Not(Box<CfgExpr>),
+    /// A bare predicate, e.g. `unix` or `target_os = "linux"`
+    Predicate { key: String, value: Option<String> },
+}
+
+impl CfgExpr {
+    fn eval(&self, cfgs: &HashMap<&str, &str>) -> bool {
+        match *self {
+            CfgExpr::All(ref exprs) => exprs.iter().all(|e| e.eval(cfgs)),
+            CfgExpr::Any(ref exprs) => exprs.iter().any(|e| e.eval(cfgs)),
+            CfgExpr::Not(ref expr) => !expr.eval(cfgs),
+            CfgExpr::Predicate { ref key, ref value } => match *value {
+                Some(ref value) => cfgs.get(key.as_str()) == Some(&value.as_str()),
+                None => cfgs.contains_key(key.as_str()),
+            },
+        }
+    }
+}
+
+/// Parses the inside of a `cfg(...)` expression (without the surrounding
+/// `cfg(` / `)`).
+fn parse_expr(input: &str) -> Option<CfgExpr> {
+    let input = input.trim();
+    if let Some(rest) = strip_call(input, "all") {
+        return Some(CfgExpr::All(parse_list(rest)?));
+    }
+    if let Some(rest) = strip_call(input, "any") {
+        return Some(CfgExpr::Any(parse_list(rest)?));
+    }
+    if let Some(rest) = strip_call(input, "not") {
+        let mut inner = parse_list(rest)?;
+        if inner.len() != 1 {
+            return None;
+        }
+        return Some(CfgExpr::Not(Box::new(inner.remove(0))));
+    }
+    parse_predicate(input)
+}
+
+fn strip_call<'a>(input: &'a str, name: &str) -> Option<&'a str> {
+    let input = input.trim();
+    if input.starts_with(name) && input[name.len()..].trim_start().starts_with('(') && input.ends_with(')') {
+        let after_name = input[name.len()..].trim_start();
+        Some(&after_name[1..after_name.len() - 1])
+    } else {
+        None
+    }
+}
+
+/// Splits a comma-separated list of `cfg` predicates, respecting nested
+/// parentheses, and parses each one.
+fn parse_list(input: &str) -> Option<Vec<CfgExpr>> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let bytes = input.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'(' => depth += 1,
+            b')' => depth -= 1,
+            b',' if depth == 0 => {
+                parts.push(&input[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    let tail = input[start..].trim();
+    if !tail.is_empty() {
+        parts.push(tail);
+    }
+    parts.into_iter().map(parse_expr).collect()
+}
+
+fn parse_predicate(input: &str) -> Option<CfgExpr> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    if let Some(eq) = input.find('=') {
+        let key = input[..eq].trim().to_string();
+        let value = input[eq + 1..].trim().trim_matches('"').to_string();
+        Some(CfgExpr::Predicate {
+            key,
+            value: Some(value),
+        })
+    } else {
+        Some(CfgExpr::Predicate {
+            key: input.to_string(),
+            value: None,
+        })
+    }
+}
+
+/// Returns the known `cfg` key/value pairs for a given target triple, e.g.
+/// `target_os`, `target_arch`, `target_env`, `target_family`, plus the
+/// `unix`/`windows` flags.
+fn cfgs_for_triple(triple: &str) -> HashMap<&str, &str> {
+    let mut cfgs = HashMap::new();
+
+    let arch = triple.split('-').next().unwrap_or("");
+    let target_arch = match arch {
+        "x86_64" => "x86_64",
+        "i686" | "i586" => "x86",
+        "aarch64" => "aarch64",
+        "armv7" | "arm" => "arm",
+        other => other,
+    };
+    cfgs.insert("target_arch", target_arch);
+
+    let (os, env) = if triple.contains("windows") {
+        ("windows", if triple.contains("gnu") { "gnu" } else { "msvc" })
+    } else if triple.contains("darwin") || triple.contains("apple") {
+        ("macos", "")
+    } else if triple.contains("linux") {
+        ("linux", if triple.contains("musl") { "musl" } else { "gnu" })
+    } else {
+        ("", "")
+    };
+    cfgs.insert("target_os", os);
+    cfgs.insert("target_env", env);
+
+    let family = if os == "windows" { "windows" } else { "unix" };
+    cfgs.insert("target_family", family);
+    if family == "unix" {
+        cfgs.insert("unix", "");
+    } else {
+        cfgs.insert("windows", "");
+    }
+
+    cfgs
+}
+
+/// Returns whether `target` (a `cfg(...)` expression or an explicit target
+/// triple, as found in `Dependency::target`) is active for `triple`.
+pub fn target_matches(target: &str, triple: &str) -> bool {
+    let cfgs = cfgs_for_triple(triple);
+    if target.starts_with("cfg(") && target.ends_with(')') {
+        let expr = parse_expr(&target[4..target.len() - 1]);
+        match expr {
+            Some(expr) => expr.eval(&cfgs),
+            // An expression we failed to parse is conservatively treated as inactive.
+            None => false,
+        }
+    } else {
+        target == triple
+    }
+}
+
+impl ::Package {
+    /// Returns the subset of this package's dependencies that are active
+    /// when building for `triple`.
+    pub fn dependencies_for_target(&self, triple: &str) -> Vec<&Dependency> {
+        self.dependencies
+            .iter()
+            .filter(|dep| match dep.target {
+                Some(ref target) => target_matches(target, triple),
+                None => true,
+            })
+            .collect()
+    }
+}
+
+impl ::Resolve {
+    /// Walks the dependency graph starting at `root` and, for every package
+    /// transitively reachable from it, returns the set of edge targets
+    /// (`None` meaning "always") under which at least one path reaches it.
+    ///
+    /// This mirrors the per-dependency `PlatformSet` gnrt computes, without
+    /// composing constraints along a path: a dependency's entry is the union
+    /// of the targets of the edges that point directly at it anywhere in the
+    /// graph reachable from `root`.
+    pub fn dependency_platforms(&self, root: &str) -> HashMap<String, HashSet<Option<String>>> {
+        let by_id: HashMap<&str, &::Node> = self.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+        let mut result: HashMap<String, HashSet<Option<String>>> = HashMap::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![root.to_string()];
+
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id.clone()) {
+                continue;
+            }
+            let node = match by_id.get(id.as_str()) {
+                Some(node) => node,
+                None => continue,
+            };
+            for dep in &node.deps {
+                let targets = result.entry(dep.pkg.clone()).or_default();
+                if dep.dep_kinds.is_empty() {
+                    targets.insert(None);
+                } else {
+                    for dep_kind in &dep.dep_kinds {
+                        targets.insert(dep_kind.target.clone());
+                    }
+                }
+                stack.push(dep.pkg.clone());
+            }
+        }
+
+        result
+    }
+}
@@ -0,0 +1,78 @@
+extern crate cargo_metadata;
+#[macro_use]
+extern crate serde_json;
+
+use cargo_metadata::{audit_report, Metadata};
+
+fn metadata() -> Metadata {
+    serde_json::from_value(json!({
+        "packages": [
+            {
+                "name": "root",
+                "version": "0.1.0",
+                "id": "root 0.1.0 (path+file:///root)",
+                "dependencies": [],
+                "targets": [],
+                "features": {},
+                "manifest_path": "/root/Cargo.toml"
+            },
+            {
+                "name": "left",
+                "version": "0.1.0",
+                "id": "left 0.1.0",
+                "source": "registry+https://github.com/rust-lang/crates.io-index",
+                "dependencies": [],
+                "targets": [],
+                "features": {},
+                "manifest_path": "/registry/left/Cargo.toml"
+            },
+            {
+                "name": "shared",
+                "version": "0.1.0",
+                "id": "shared 0.1.0",
+                "source": "registry+https://github.com/rust-lang/crates.io-index",
+                "dependencies": [],
+                "targets": [],
+                "features": {},
+                "manifest_path": "/registry/shared/Cargo.toml"
+            }
+        ],
+        "workspace_members": ["root 0.1.0 (path+file:///root)"],
+        "resolve": {
+            "nodes": [
+                {"id": "root 0.1.0 (path+file:///root)", "dependencies": ["left 0.1.0", "shared 0.1.0"]},
+                {"id": "left 0.1.0", "dependencies": ["shared 0.1.0"]},
+                {"id": "shared 0.1.0", "dependencies": []}
+            ]
+        },
+        "version": 1
+    }))
+    .unwrap()
+}
+
+#[test]
+fn roots_come_first_and_packages_are_deduplicated() {
+    let metadata = metadata();
+    let report = audit_report(&metadata).expect("resolve was present");
+
+    assert_eq!(report.packages.len(), 3);
+    assert!(report.packages[0].root);
+    assert_eq!(report.packages[0].name, "root");
+
+    let shared_count = report.packages.iter().filter(|p| p.name == "shared").count();
+    assert_eq!(shared_count, 1, "shared must only appear once");
+
+    let shared_index = report.packages.iter().position(|p| p.name == "shared").unwrap();
+    let left_index = report.packages.iter().position(|p| p.name == "left").unwrap();
+    assert!(
+        left_index < shared_index,
+        "left must be ordered before its dependency shared"
+    );
+}
+
+#[test]
+fn none_without_a_resolved_graph() {
+    let mut metadata = metadata();
+    metadata.resolve = None;
+    assert!(audit_report(&metadata).is_none());
+}
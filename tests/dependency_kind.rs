@@ -0,0 +1,76 @@
+extern crate cargo_metadata;
+#[macro_use]
+extern crate serde_json;
+
+use cargo_metadata::{Dependency, DependencyKind};
+
+#[test]
+fn null_kind_deserializes_to_normal() {
+    let dep: Dependency = serde_json::from_value(json!({
+        "name": "serde",
+        "req": "^1.0",
+        "kind": null,
+        "optional": false,
+        "uses_default_features": true,
+        "features": [],
+        "target": null
+    }))
+    .unwrap();
+    assert_eq!(dep.kind, DependencyKind::Normal);
+}
+
+#[test]
+fn missing_kind_deserializes_to_normal() {
+    let dep: Dependency = serde_json::from_value(json!({
+        "name": "serde",
+        "req": "^1.0",
+        "optional": false,
+        "uses_default_features": true,
+        "features": [],
+        "target": null
+    }))
+    .unwrap();
+    assert_eq!(dep.kind, DependencyKind::Normal);
+}
+
+#[test]
+fn unrecognized_kind_string_falls_back_to_unknown() {
+    let dep: Dependency = serde_json::from_value(json!({
+        "name": "serde",
+        "req": "^1.0",
+        "kind": "some-future-kind",
+        "optional": false,
+        "uses_default_features": true,
+        "features": [],
+        "target": null
+    }))
+    .unwrap();
+    assert_eq!(dep.kind, DependencyKind::Unknown);
+}
+
+#[test]
+fn dev_and_build_still_parse() {
+    let dev: Dependency = serde_json::from_value(json!({
+        "name": "serde",
+        "req": "^1.0",
+        "kind": "dev",
+        "optional": false,
+        "uses_default_features": true,
+        "features": [],
+        "target": null
+    }))
+    .unwrap();
+    assert_eq!(dev.kind, DependencyKind::Development);
+
+    let build: Dependency = serde_json::from_value(json!({
+        "name": "serde",
+        "req": "^1.0",
+        "kind": "build",
+        "optional": false,
+        "uses_default_features": true,
+        "features": [],
+        "target": null
+    }))
+    .unwrap();
+    assert_eq!(build.kind, DependencyKind::Build);
+}
@@ -0,0 +1,58 @@
+extern crate cargo_metadata;
+#[macro_use]
+extern crate serde_json;
+
+use cargo_metadata::Package;
+
+fn package(features: serde_json::Value) -> Package {
+    serde_json::from_value(json!({
+        "name": "demo",
+        "version": "0.1.0",
+        "id": "demo 0.1.0",
+        "dependencies": [],
+        "targets": [],
+        "features": features,
+        "manifest_path": "Cargo.toml"
+    }))
+    .unwrap()
+}
+
+fn set(names: &[&str]) -> ::std::collections::BTreeSet<String> {
+    names.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn seeds_from_activated_and_default() {
+    let pkg = package(json!({"default": ["a"], "a": [], "b": []}));
+    assert_eq!(pkg.resolve_features(&["b"], true), set(&["a", "b", "default"]));
+}
+
+#[test]
+fn without_default_only_activated_is_seeded() {
+    let pkg = package(json!({"default": ["a"], "a": [], "b": []}));
+    assert_eq!(pkg.resolve_features(&["b"], false), set(&["b"]));
+}
+
+#[test]
+fn expands_to_a_fixpoint() {
+    let pkg = package(json!({"a": ["b"], "b": ["c"], "c": []}));
+    assert_eq!(pkg.resolve_features(&["a"], false), set(&["a", "b", "c"]));
+}
+
+#[test]
+fn tolerates_cycles() {
+    let pkg = package(json!({"a": ["b"], "b": ["a"]}));
+    assert_eq!(pkg.resolve_features(&["a"], false), set(&["a", "b"]));
+}
+
+#[test]
+fn dep_slash_feature_does_not_pollute_the_local_set() {
+    let pkg = package(json!({"std": ["serde/std"]}));
+    assert_eq!(pkg.resolve_features(&["std"], false), set(&["std"]));
+}
+
+#[test]
+fn unknown_feature_is_a_no_op_optional_dependency() {
+    let pkg = package(json!({}));
+    assert!(pkg.resolve_features(&["some_optional_dep"], false).is_empty());
+}
@@ -0,0 +1,58 @@
+extern crate cargo_metadata;
+
+use cargo_metadata::target_matches;
+
+#[test]
+fn bare_triple_matches_only_itself() {
+    assert!(target_matches(
+        "x86_64-pc-windows-msvc",
+        "x86_64-pc-windows-msvc"
+    ));
+    assert!(!target_matches(
+        "x86_64-pc-windows-msvc",
+        "x86_64-unknown-linux-gnu"
+    ));
+}
+
+#[test]
+fn cfg_unix_and_windows() {
+    assert!(target_matches("cfg(unix)", "x86_64-unknown-linux-gnu"));
+    assert!(!target_matches("cfg(unix)", "x86_64-pc-windows-msvc"));
+    assert!(target_matches("cfg(windows)", "x86_64-pc-windows-msvc"));
+    assert!(!target_matches("cfg(windows)", "x86_64-unknown-linux-gnu"));
+}
+
+#[test]
+fn cfg_target_os_and_env() {
+    assert!(target_matches(
+        "cfg(target_os = \"linux\")",
+        "x86_64-unknown-linux-gnu"
+    ));
+    assert!(!target_matches(
+        "cfg(target_os = \"macos\")",
+        "x86_64-unknown-linux-gnu"
+    ));
+    assert!(target_matches(
+        "cfg(target_env = \"musl\")",
+        "x86_64-unknown-linux-musl"
+    ));
+    assert!(!target_matches(
+        "cfg(target_env = \"musl\")",
+        "x86_64-unknown-linux-gnu"
+    ));
+}
+
+#[test]
+fn cfg_all_any_not_nesting() {
+    let expr = "cfg(all(unix, any(target_os = \"linux\", target_os = \"macos\")))";
+    assert!(target_matches(expr, "x86_64-unknown-linux-gnu"));
+    assert!(!target_matches(expr, "x86_64-pc-windows-msvc"));
+
+    assert!(target_matches("cfg(not(windows))", "x86_64-unknown-linux-gnu"));
+    assert!(!target_matches("cfg(not(windows))", "x86_64-pc-windows-msvc"));
+}
+
+#[test]
+fn unparseable_cfg_is_treated_as_inactive() {
+    assert!(!target_matches("cfg(not())", "x86_64-unknown-linux-gnu"));
+}